@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+
+use crate::commit::CommitInfo;
+use crate::conventional::{has_breaking_footer, ConventionalCommit};
+use crate::version::Version;
+
+/// Conventional Commit types rendered as their own changelog section, in
+/// display order. Types not listed here fall under "Other".
+const SECTIONS: &[(&str, &str)] = &[
+    ("feat", "Features"),
+    ("fix", "Bug Fixes"),
+    ("perf", "Performance"),
+    ("refactor", "Refactoring"),
+    ("docs", "Documentation"),
+    ("style", "Styles"),
+    ("test", "Tests"),
+    ("chore", "Chores"),
+    ("ci", "CI"),
+];
+
+/// Renders a grouped Markdown changelog section for `version`, released on
+/// `date`, grouping `commits` by Conventional Commit type. Breaking changes
+/// are additionally collected under their own `### Breaking Changes` heading
+/// at the top of the section.
+pub fn render(version: &Version, date: &str, commits: &[CommitInfo]) -> String {
+    let mut breaking = Vec::new();
+    let mut by_kind: HashMap<&str, Vec<String>> = HashMap::new();
+    let mut other = Vec::new();
+
+    for commit in commits {
+        match ConventionalCommit::parse(&commit.summary) {
+            Some(parsed) => {
+                let entry = format_entry(parsed.scope, parsed.description, commit);
+                if parsed.breaking || has_breaking_footer(&commit.body) {
+                    breaking.push(entry.clone());
+                }
+                if SECTIONS.iter().any(|(kind, _)| *kind == parsed.kind) {
+                    by_kind.entry(parsed.kind).or_default().push(entry);
+                } else {
+                    other.push(entry);
+                }
+            }
+            None => other.push(format_entry(None, &commit.summary, commit)),
+        }
+    }
+
+    let mut changelog = format!("## {} - {}\n", version, date);
+
+    if !breaking.is_empty() {
+        changelog.push_str(&render_section("Breaking Changes", &breaking));
+    }
+    for (kind, heading) in SECTIONS {
+        if let Some(entries) = by_kind.get(kind) {
+            changelog.push_str(&render_section(heading, entries));
+        }
+    }
+    if !other.is_empty() {
+        changelog.push_str(&render_section("Other", &other));
+    }
+
+    changelog
+}
+
+fn render_section(heading: &str, entries: &[String]) -> String {
+    format!("\n### {}\n{}\n", heading, entries.join("\n"))
+}
+
+fn format_entry(scope: Option<&str>, description: &str, commit: &CommitInfo) -> String {
+    match scope {
+        Some(scope) => format!("- {}: {} ({})", scope, description, commit.short_sha()),
+        None => format!("- {} ({})", description, commit.short_sha()),
+    }
+}
@@ -0,0 +1,15 @@
+use git2::Oid;
+
+/// A single commit collected between the previous tag and `HEAD`.
+pub struct CommitInfo {
+    pub id: Oid,
+    pub summary: String,
+    pub body: String,
+}
+
+impl CommitInfo {
+    /// Short, human-readable form of the commit id, as used in changelogs.
+    pub fn short_sha(&self) -> String {
+        format!("{:.7}", self.id)
+    }
+}
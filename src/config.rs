@@ -0,0 +1,83 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::version::SubVersion;
+
+/// Project-level defaults loaded from a `.tagger.toml` file at the repo
+/// root. Any field left unset keeps tagger's built-in default, or is
+/// overridden by an explicit CLI flag.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Prefix prepended to the semantic version when formatting and parsing
+    /// tags, e.g. `"v"` in `v1.2.0`.
+    pub tag_prefix: Option<String>,
+
+    /// Identifier used for prerelease versions, e.g. `"pre"` in
+    /// `v1.2.0-pre3`.
+    pub prerelease_identifier: Option<String>,
+
+    /// Whether to open an interactive editor on the tag message before
+    /// creating the tag.
+    pub interactive_editor: Option<bool>,
+
+    /// Whether to push the new tag without prompting for confirmation.
+    pub auto_push: Option<bool>,
+
+    /// Default subversion to increment when prompting interactively.
+    pub default_subversion: Option<SubVersion>,
+
+    /// Path prefixes to scope version and changelog computation to, for
+    /// monorepos where a tag history is shared across components. A commit
+    /// is only considered if it touches at least one of these paths. Empty
+    /// means no scoping: every commit is considered.
+    pub paths: Vec<String>,
+
+    /// Shell commands to run, in order, after the tag is created and before
+    /// it is pushed. Each is expanded through [`crate::hooks::render_template`]
+    /// first. The tag/push flow aborts if any hook exits non-zero.
+    pub hooks: Vec<String>,
+}
+
+impl Config {
+    /// Loads `.tagger.toml` from `repo_root`. Returns the all-default config
+    /// if no such file exists.
+    pub fn load(repo_root: &Path) -> Result<Self> {
+        let path = repo_root.join(".tagger.toml");
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let config: Self = toml::from_str(&contents)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Checks config values that can't be represented in the type system,
+    /// so an invalid `.tagger.toml` fails loudly here instead of panicking
+    /// later wherever the value is used to build a tag.
+    fn validate(&self) -> Result<()> {
+        if let Some(identifier) = &self.prerelease_identifier {
+            anyhow::ensure!(
+                !identifier.is_empty()
+                    && identifier.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'),
+                "`prerelease_identifier` must be a non-empty semver identifier \
+                 (ASCII letters, digits and hyphens only), got `{}`",
+                identifier
+            );
+        }
+        Ok(())
+    }
+
+    pub fn tag_prefix(&self) -> &str {
+        self.tag_prefix.as_deref().unwrap_or("v")
+    }
+
+    pub fn prerelease_identifier(&self) -> &str {
+        self.prerelease_identifier.as_deref().unwrap_or("pre")
+    }
+}
@@ -0,0 +1,78 @@
+use crate::commit::CommitInfo;
+use crate::version::SubVersion;
+
+/// The parsed `type(scope)!: description` header of a Conventional Commit.
+pub struct ConventionalCommit<'a> {
+    pub kind: &'a str,
+    pub scope: Option<&'a str>,
+    pub description: &'a str,
+    pub breaking: bool,
+}
+
+impl<'a> ConventionalCommit<'a> {
+    /// Parses a commit summary against the Conventional Commits grammar:
+    /// `type(scope)!: description`. Returns `None` if the summary has no
+    /// `:` separator, i.e. does not follow the convention at all.
+    pub fn parse(summary: &'a str) -> Option<Self> {
+        let (header, description) = summary.split_once(':')?;
+        let header = header.trim();
+        let description = description.trim();
+
+        let (breaking, header) = match header.strip_suffix('!') {
+            Some(header) => (true, header),
+            None => (false, header),
+        };
+
+        let (kind, scope) = match header.split_once('(') {
+            Some((kind, rest)) => (kind, rest.strip_suffix(')')),
+            None => (header, None),
+        };
+
+        Some(Self {
+            kind,
+            scope,
+            description,
+            breaking,
+        })
+    }
+}
+
+/// Whether a commit body carries a `BREAKING CHANGE` / `BREAKING-CHANGE`
+/// footer, per the Conventional Commits specification.
+pub fn has_breaking_footer(body: &str) -> bool {
+    body.lines().any(|line| {
+        let line = line.trim_start();
+        line.starts_with("BREAKING CHANGE:") || line.starts_with("BREAKING-CHANGE:")
+    })
+}
+
+/// Determines the strongest version bump implied by `commits`, per the
+/// Conventional Commits specification: a breaking change (`!` or a
+/// `BREAKING CHANGE` footer) implies [`SubVersion::Major`], `feat` implies
+/// [`SubVersion::Minor`], `fix`/`perf` implies [`SubVersion::Patch`], and
+/// anything else (`docs`, `chore`, `refactor`, `style`, `test`, `ci`, or an
+/// unparseable summary) contributes nothing. Returns `None` if no commit
+/// implies a bump.
+pub fn detect_bump(commits: &[CommitInfo]) -> Option<SubVersion> {
+    commits.iter().filter_map(bump_for_commit).max_by_key(bump_rank)
+}
+
+fn bump_for_commit(commit: &CommitInfo) -> Option<SubVersion> {
+    let parsed = ConventionalCommit::parse(&commit.summary)?;
+    if parsed.breaking || has_breaking_footer(&commit.body) {
+        return Some(SubVersion::Major);
+    }
+    match parsed.kind {
+        "feat" => Some(SubVersion::Minor),
+        "fix" | "perf" => Some(SubVersion::Patch),
+        _ => None,
+    }
+}
+
+fn bump_rank(bump: &SubVersion) -> u8 {
+    match bump {
+        SubVersion::Major => 2,
+        SubVersion::Minor => 1,
+        SubVersion::Patch => 0,
+    }
+}
@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use git2::{Oid, Repository};
+
+use crate::config::Config;
+use crate::version::{SubVersion, Version};
+
+/// Computes a MinVer-style version for `HEAD` from its graph distance (the
+/// "height") to the nearest reachable tag, without any prompting. If the
+/// nearest tag is a release `X.Y.Z`, the result is the next patch
+/// `X.Y.(Z+1)` with the height encoded in the prerelease; if the nearest tag
+/// is already a prerelease, its base is kept and the height is appended. If
+/// no tag is reachable, the configured default initial version is used with
+/// the height to `HEAD`'s root.
+pub fn height_version(repo: &Repository, all_tags: &[Version], config: &Config) -> Result<Version> {
+    let tags_by_commit = tags_by_commit(repo, all_tags)?;
+
+    let head = repo.head()?.peel_to_commit()?;
+    let mut memo = HashMap::new();
+    let nearest = nearest_tag(repo, head.id(), &tags_by_commit, &mut memo)?;
+
+    Ok(match nearest {
+        Some((version, height)) if version.is_prerelease() => version.append_height(height),
+        Some((version, height)) => version
+            .increment_version(SubVersion::Patch)
+            .with_height(height, config.prerelease_identifier()),
+        None => {
+            let mut revwalk = repo.revwalk()?;
+            revwalk.push_head()?;
+            let height = revwalk.count().saturating_sub(1) as u32;
+            Version::default_initial(config.tag_prefix(), config.prerelease_identifier())
+                .with_height(height, config.prerelease_identifier())
+        }
+    })
+}
+
+/// Maps each tagged commit to the highest-precedence `Version` pointing at
+/// it (a commit may carry more than one tag).
+fn tags_by_commit(repo: &Repository, all_tags: &[Version]) -> Result<HashMap<Oid, Version>> {
+    let mut tags_by_commit = HashMap::new();
+    for tag in all_tags {
+        let Ok(reference) = repo.find_reference(&tag.git_ref()) else {
+            continue;
+        };
+        let Ok(commit) = reference.peel_to_commit() else {
+            continue;
+        };
+        tags_by_commit
+            .entry(commit.id())
+            .and_modify(|existing: &mut Version| {
+                if *tag > *existing {
+                    *existing = tag.clone();
+                }
+            })
+            .or_insert_with(|| tag.clone());
+    }
+    Ok(tags_by_commit)
+}
+
+/// Walks ancestors of `commit_id` to find the closest tagged commit,
+/// returning its `Version` and the number of commits (the height) between
+/// it and `commit_id`. Memoizes per-commit results so shared ancestors of a
+/// merge are only computed once, taking the max-precedence version when
+/// multiple tagged descendants converge. Iterative (an explicit work stack
+/// rather than recursion) so a long linear history doesn't overflow the call
+/// stack.
+fn nearest_tag(
+    repo: &Repository,
+    commit_id: Oid,
+    tags_by_commit: &HashMap<Oid, Version>,
+    memo: &mut HashMap<Oid, Option<(Version, u32)>>,
+) -> Result<Option<(Version, u32)>> {
+    // First visit: push parents to resolve. Second visit (parents already
+    // memoized): fold their results into this commit's entry.
+    let mut stack = vec![(commit_id, false)];
+
+    while let Some((id, parents_resolved)) = stack.pop() {
+        if memo.contains_key(&id) {
+            continue;
+        }
+
+        if let Some(version) = tags_by_commit.get(&id) {
+            memo.insert(id, Some((version.clone(), 0)));
+            continue;
+        }
+
+        let commit = repo.find_commit(id)?;
+        if !parents_resolved {
+            stack.push((id, true));
+            for parent in commit.parents() {
+                if !memo.contains_key(&parent.id()) {
+                    stack.push((parent.id(), false));
+                }
+            }
+            continue;
+        }
+
+        let mut best: Option<(Version, u32)> = None;
+        for parent in commit.parents() {
+            if let Some((version, height)) = memo.get(&parent.id()).cloned().flatten() {
+                let candidate = (version, height + 1);
+                best = Some(match best {
+                    Some(current) if current.0 >= candidate.0 => current,
+                    _ => candidate,
+                });
+            }
+        }
+        memo.insert(id, best);
+    }
+
+    Ok(memo.get(&commit_id).cloned().flatten())
+}
@@ -0,0 +1,124 @@
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+
+use crate::version::Version;
+
+/// Expands `{{ ... }}` placeholders in `template`. Recognises `{{version}}`,
+/// `{{latest_version}}`, the dotted accessors `{{version.major}}`,
+/// `{{version.minor}}`, `{{version.patch}}`, `{{version.prerelease}}` (and
+/// the same accessors on `latest_version`), plus an optional trailing
+/// `+N`/`-N` arithmetic modifier on the numeric accessors, e.g.
+/// `{{version.major+1}}`. Literal text outside `{{ }}` spans passes through
+/// unchanged.
+pub fn render_template(
+    template: &str,
+    version: &Version,
+    latest_version: Option<&Version>,
+) -> Result<String> {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after
+            .find("}}")
+            .context("Unterminated {{ }} placeholder in hook command")?;
+        output.push_str(&resolve_placeholder(
+            after[..end].trim(),
+            version,
+            latest_version,
+        )?);
+        rest = &after[end + 2..];
+    }
+    output.push_str(rest);
+    Ok(output)
+}
+
+fn resolve_placeholder(
+    expr: &str,
+    version: &Version,
+    latest_version: Option<&Version>,
+) -> Result<String> {
+    let mut parts = expr.split('.');
+    let root = parts.next().context("Empty {{ }} placeholder")?;
+    let base = match root {
+        "version" => version,
+        "latest_version" => {
+            latest_version.with_context(|| "No latest_version is available to substitute")?
+        }
+        other => anyhow::bail!("Unknown placeholder `{}`", other),
+    };
+
+    match parts.next() {
+        None => Ok(base.to_string()),
+        Some(field) => resolve_field(base, field),
+    }
+}
+
+fn resolve_field(version: &Version, field: &str) -> Result<String> {
+    let (field, modifier) = split_modifier(field);
+    Ok(match field {
+        "major" => apply_modifier(version.semver.major as i64, modifier)?.to_string(),
+        "minor" => apply_modifier(version.semver.minor as i64, modifier)?.to_string(),
+        "patch" => apply_modifier(version.semver.patch as i64, modifier)?.to_string(),
+        "prerelease" => {
+            anyhow::ensure!(
+                modifier.is_none(),
+                "`prerelease` does not support arithmetic modifiers"
+            );
+            version.semver.pre.as_str().to_string()
+        }
+        other => anyhow::bail!("Unknown version field `{}`", other),
+    })
+}
+
+/// Splits a trailing `+N`/`-N` arithmetic modifier off a field name, if any.
+fn split_modifier(field: &str) -> (&str, Option<(char, i64)>) {
+    for (i, c) in field.char_indices() {
+        if matches!(c, '+' | '-') {
+            if let Ok(n) = field[i + 1..].parse::<i64>() {
+                return (&field[..i], Some((c, n)));
+            }
+        }
+    }
+    (field, None)
+}
+
+fn apply_modifier(value: i64, modifier: Option<(char, i64)>) -> Result<i64> {
+    Ok(match modifier {
+        Some(('+', n)) => value + n,
+        Some(('-', n)) => value - n,
+        Some((op, _)) => anyhow::bail!("Unknown modifier operator `{}`", op),
+        None => value,
+    })
+}
+
+/// Runs `commands` in order, each expanded through [`render_template`], with
+/// the working directory set to `repo_root`. Aborts on the first command
+/// that exits non-zero.
+pub fn run_hooks(
+    commands: &[String],
+    repo_root: &Path,
+    version: &Version,
+    latest_version: Option<&Version>,
+) -> Result<()> {
+    for command in commands {
+        let expanded = render_template(command, version, latest_version)?;
+        println!("Running hook: {}", expanded);
+        let status = Command::new("sh")
+            .arg("-c")
+            .arg(&expanded)
+            .current_dir(repo_root)
+            .status()
+            .with_context(|| format!("Failed to run hook `{}`", expanded))?;
+        anyhow::ensure!(
+            status.success(),
+            "Hook `{}` exited with {}",
+            expanded,
+            status
+        );
+    }
+    Ok(())
+}
@@ -1,3 +1,9 @@
+pub mod changelog;
+pub mod commit;
+pub mod config;
+pub mod conventional;
+pub mod height;
+pub mod hooks;
 pub mod version;
 
 use std::io::{self, Write};
@@ -5,22 +11,54 @@ use std::process::Command;
 use std::str::FromStr;
 
 use anyhow::Context;
+use chrono::Local;
+use commit::CommitInfo;
+use config::Config;
 use console::Style;
 use dialoguer::Confirm;
 use dialoguer::{theme::ColorfulTheme, Editor, Input, Select};
-use git2::{DescribeFormatOptions, DescribeOptions, Repository};
+use git2::{Commit, DescribeFormatOptions, DescribeOptions, Repository};
 use strum::VariantNames;
 use version::{SubVersion, Version};
 
-pub fn tagger(
-    repo: &Repository,
-    next_version: Option<Version>,
-    interactive_editor: bool,
-    prompt_push: bool,
-) -> anyhow::Result<()> {
-    println!("Fetching tags from remote...");
+/// CLI-level inputs to [`tagger`], already merged with [`Config`] defaults
+/// by the caller.
+pub struct TaggerOptions {
+    pub next_version: Option<Version>,
+    pub interactive_editor: bool,
+    pub prompt_push: bool,
+    pub conventional: bool,
+    pub append_changelog: bool,
+    pub paths: Vec<String>,
+    pub height: bool,
+    pub print: bool,
+    pub commit_sha: bool,
+}
+
+pub fn tagger(repo: &Repository, options: TaggerOptions, config: &Config) -> anyhow::Result<()> {
+    let TaggerOptions {
+        next_version,
+        interactive_editor,
+        prompt_push,
+        conventional,
+        append_changelog,
+        paths,
+        height,
+        print,
+        commit_sha,
+    } = options;
+
+    // With --print, stdout is reserved for the resolved version/sha so it
+    // can be captured with `$(tagger --print)`; send progress to stderr.
+    match print {
+        true => eprintln!("Fetching tags from remote..."),
+        false => println!("Fetching tags from remote..."),
+    }
     let fetch_output = Command::new("git").arg("fetch").arg("--tags").output()?;
-    io::stdout().write_all(&fetch_output.stdout)?;
+    match print {
+        true => io::stderr().write_all(&fetch_output.stdout)?,
+        false => io::stdout().write_all(&fetch_output.stdout)?,
+    }
     anyhow::ensure!(
         fetch_output.status.success(),
         "Failed to fetch tags: {}",
@@ -37,12 +75,12 @@ pub fn tagger(
     let all_tags = repo
         .tag_names(None)?
         .iter()
-        .filter_map(|name| Version::parse(name?).ok())
+        .filter_map(|name| Version::parse(name?, config.tag_prefix(), config.prerelease_identifier()).ok())
         .collect::<Vec<_>>();
 
     let latest_release = all_tags
         .iter()
-        .filter(|version| version.0.pre.is_empty())
+        .filter(|version| !version.is_prerelease())
         .max()
         .cloned();
 
@@ -52,47 +90,62 @@ pub fn tagger(
             description.format(Some(DescribeFormatOptions::new().abbreviated_size(0)))
         })
         .ok()
-        .and_then(|name: String| Version::parse(&name).ok())
-        .filter(|version| !version.0.pre.is_empty());
+        .and_then(|name: String| {
+            Version::parse(&name, config.tag_prefix(), config.prerelease_identifier()).ok()
+        })
+        .filter(|version| version.is_prerelease());
 
-    let commit_history = get_commit_history(repo, &all_tags)?;
+    let commit_history = get_commit_history(repo, &all_tags, &paths)?;
     print_summary(
         &latest_release,
         &latest_current_prerelease,
         &all_tags,
         &commit_history,
+        print,
     );
 
     // Determine new tag version
-    let next_tag = match next_version {
-        Some(version) => {
-            if all_tags.contains(&version) {
-                return Err(anyhow::format_err!("Version already exists"));
-            }
-            version
-        }
-        None => {
-            // Generate proposal for new tag version
-            let branch_name = head.name().context("Could not get branch name")?;
-            let next_tag_proposal = match branch_name {
-                "refs/heads/main" | "refs/heads/master" => prompt_increment(latest_release),
-                _ => latest_current_prerelease
-                    .unwrap_or(prompt_increment(latest_release)?)
-                    .increment_prerelease(1),
-            }?
-            .resolve_collision(&all_tags)?;
-            prompt_next_tag(&next_tag_proposal)?
+    let next_tag = resolve_next_tag(
+        repo,
+        head,
+        next_version,
+        conventional,
+        height,
+        print,
+        config,
+        &all_tags,
+        &commit_history,
+        latest_release.clone(),
+        latest_current_prerelease,
+    )?;
+
+    if print {
+        match commit_sha {
+            true => println!("{}", head_commit.id()),
+            false => println!("{}", next_tag),
         }
-    };
+        return Ok(());
+    }
 
-    let mut message = format!(
-        "release_notes:\n{}",
-        commit_history.join("\n").replace(':', "")
+    let changelog_entry = changelog::render(
+        &next_tag,
+        &Local::now().format("%Y-%m-%d").to_string(),
+        &commit_history,
     );
+    let mut message = changelog_entry.clone();
     if interactive_editor {
         message = Editor::new().edit(&message)?.unwrap_or_default();
     }
 
+    if append_changelog {
+        let changelog_path = repo
+            .workdir()
+            .context("Repository has no working directory")?
+            .join("CHANGELOG.md");
+        let previous = std::fs::read_to_string(&changelog_path).unwrap_or_default();
+        std::fs::write(&changelog_path, format!("{}\n{}", changelog_entry, previous))?;
+    }
+
     // Create new tag
     let _created_ref = repo.tag(
         &next_tag.to_string(),
@@ -102,6 +155,13 @@ pub fn tagger(
         false,
     )?;
 
+    if !config.hooks.is_empty() {
+        let repo_root = repo
+            .workdir()
+            .context("Repository has no working directory")?;
+        hooks::run_hooks(&config.hooks, repo_root, &next_tag, latest_release.as_ref())?;
+    }
+
     // Push tag
     if !prompt_push || Confirm::new().with_prompt("\nPush tag?").interact()? {
         let push_output = Command::new("git").arg("push").arg("--tags").output()?;
@@ -116,88 +176,228 @@ pub fn tagger(
     Ok(())
 }
 
-/// Prints a summary of current tags
+/// Resolves the next tag version: an explicit `next_version` if given, the
+/// height-based version (returned without prompting) if `height` is set,
+/// otherwise a branch-aware proposal (interactive or Conventional Commits)
+/// confirmed with the user. Shared by the tag-creating and
+/// `--print` paths of [`tagger`]. With `print` set, never prompts: `--print`
+/// is meant for non-interactive CI capture via `$(tagger --print)`, so the
+/// resolved proposal is returned as-is, and resolution errors out instead of
+/// falling back to an interactive prompt.
+#[allow(clippy::too_many_arguments)]
+fn resolve_next_tag(
+    repo: &Repository,
+    head: &git2::Reference,
+    next_version: Option<Version>,
+    conventional: bool,
+    height: bool,
+    print: bool,
+    config: &Config,
+    all_tags: &[Version],
+    commit_history: &[CommitInfo],
+    latest_release: Option<Version>,
+    latest_current_prerelease: Option<Version>,
+) -> anyhow::Result<Version> {
+    match next_version {
+        Some(version) => {
+            if all_tags.contains(&version) {
+                return Err(anyhow::format_err!("Version already exists"));
+            }
+            Ok(version)
+        }
+        None if height => {
+            Ok(height::height_version(repo, all_tags, config)?.resolve_collision(all_tags)?)
+        }
+        None => {
+            // Generate proposal for new tag version
+            let branch_name = head.name().context("Could not get branch name")?;
+            let resolve_increment = |release: Option<Version>| -> anyhow::Result<Version> {
+                match conventional {
+                    true => match conventional::detect_bump(commit_history) {
+                        Some(bump) => Ok(release
+                            .unwrap_or_else(|| {
+                                Version::default_initial(
+                                    config.tag_prefix(),
+                                    config.prerelease_identifier(),
+                                )
+                            })
+                            .increment_version(bump)),
+                        None if print => Err(anyhow::format_err!(
+                            "Cannot resolve a version non-interactively for --print: no \
+                             Conventional Commits bump detected. Pass --height, or an explicit \
+                             version, for CI use."
+                        )),
+                        None => prompt_increment(release, config),
+                    },
+                    false if print => Err(anyhow::format_err!(
+                        "Cannot resolve a version non-interactively for --print. Pass \
+                         --conventional, --height, or an explicit version for CI use."
+                    )),
+                    false => prompt_increment(release, config),
+                }
+            };
+            let next_tag_proposal = match branch_name {
+                "refs/heads/main" | "refs/heads/master" => resolve_increment(latest_release.clone()),
+                _ => {
+                    let base = match latest_current_prerelease {
+                        Some(version) => version,
+                        None => resolve_increment(latest_release)?,
+                    };
+                    base.increment_prerelease(1)
+                }
+            }?
+            .resolve_collision(all_tags)?;
+            match print {
+                true => Ok(next_tag_proposal),
+                false => prompt_next_tag(&next_tag_proposal, config),
+            }
+        }
+    }
+}
+
+/// Prints a summary of current tags. With `print` set, everything is sent
+/// to stderr so stdout stays reserved for the resolved version/sha.
 fn print_summary(
     latest_release: &Option<Version>,
     latest_prerelease: &Option<Version>,
     all_tags: &[Version],
-    commit_messages: &[String],
+    commits: &[CommitInfo],
+    print: bool,
 ) {
     let commit_message_style = Style::new().dim().italic();
+    let emit = |line: String| match print {
+        true => eprintln!("{}", line),
+        false => println!("{}", line),
+    };
 
-    println!("\nLatest tags:");
+    emit("\nLatest tags:".to_string());
     if let Some(version) = latest_release {
-        print_tag(version, "main")
+        print_tag(version, "main", print)
     }
     if let Some(version) = latest_prerelease {
-        print_tag(version, "current branch")
+        print_tag(version, "current branch", print)
     }
 
-    println!("\nAll current prereleases:");
+    emit("\nAll current prereleases:".to_string());
     for version in all_tags
         .iter()
-        .filter(|version| !version.0.pre.is_empty())
-        .filter(|&version| version.gt(&latest_release.to_owned().unwrap_or_default()))
+        .filter(|version| version.is_prerelease())
+        .filter(|&version| latest_release.as_ref().is_none_or(|release| version > release))
     {
-        print_tag(version, "")
+        print_tag(version, "", print)
     }
 
-    println!("\nCommits since latest tag:");
-    for message in commit_messages {
-        println!("{}", commit_message_style.apply_to(message));
+    emit("\nCommits since latest tag:".to_string());
+    for commit in commits {
+        emit(
+            commit_message_style
+                .apply_to(format!(" - {} {}", commit.short_sha(), commit.summary))
+                .to_string(),
+        );
     }
-    println!();
+    emit(String::new());
 }
 
-/// Prints a tag nicely
-fn print_tag(version: &Version, annotation: &str) {
+/// Prints a tag nicely, to stderr when `print` is set
+fn print_tag(version: &Version, annotation: &str, print: bool) {
     let tag_style = Style::new().yellow().bold();
-    println!(
+    let line = format!(
         " {} {}",
         tag_style.apply_to(format!("{: <14}", version)),
         annotation
     );
+    match print {
+        true => eprintln!("{}", line),
+        false => println!("{}", line),
+    }
 }
 
 /// Proposes new tag to user and prompts for confirmation
-fn prompt_next_tag(proposal: &Version) -> anyhow::Result<Version> {
+fn prompt_next_tag(proposal: &Version, config: &Config) -> anyhow::Result<Version> {
     let input: String = Input::new()
         .with_prompt("\nEnter new tag version")
         .default(proposal.to_string())
         .interact_text()?;
-    Version::parse(&input)
+    Version::parse(&input, config.tag_prefix(), config.prerelease_identifier())
 }
 
-/// Determine message based on commit history
-fn get_commit_history(repo: &Repository, all_tags: &[Version]) -> anyhow::Result<Vec<String>> {
+/// Collects the commits reachable from `HEAD` but not from any existing tag,
+/// optionally scoped to those touching one of `paths` (a monorepo component).
+/// A root commit (no parent) is always considered to touch every path.
+fn get_commit_history(
+    repo: &Repository,
+    all_tags: &[Version],
+    paths: &[String],
+) -> anyhow::Result<Vec<CommitInfo>> {
     let mut revwalk = repo.revwalk()?;
     revwalk.push_head()?;
     for tag in all_tags {
         revwalk.hide_ref(&tag.git_ref())?;
     }
-    Ok(revwalk
+    revwalk
         .filter_map(|reference| repo.find_commit(reference.ok()?).ok())
+        .filter_map(|commit| match commit_touches_paths(repo, &commit, paths) {
+            Ok(true) => Some(Ok(commit)),
+            Ok(false) => None,
+            Err(err) => Some(Err(err)),
+        })
         .map(|commit| {
-            format!(
-                " - {:.7} {}",
-                commit.id(),
-                commit.summary().unwrap_or_default()
-            )
+            commit.map(|commit| CommitInfo {
+                id: commit.id(),
+                summary: commit.summary().unwrap_or_default().to_string(),
+                body: commit.body().unwrap_or_default().to_string(),
+            })
         })
-        .collect())
+        .collect()
+}
+
+/// Whether `commit` changes a file under one of `paths`, by diffing it
+/// against its first parent. A root commit, or an empty `paths`, always
+/// counts as touching every path.
+fn commit_touches_paths(repo: &Repository, commit: &Commit, paths: &[String]) -> anyhow::Result<bool> {
+    if paths.is_empty() || commit.parent_count() == 0 {
+        return Ok(true);
+    }
+
+    let old_tree = commit.parent(0)?.tree()?;
+    let new_tree = commit.tree()?;
+    let diff = repo.diff_tree_to_tree(Some(&old_tree), Some(&new_tree), None)?;
+
+    Ok(diff.deltas().any(|delta| {
+        [delta.old_file().path(), delta.new_file().path()]
+            .into_iter()
+            .flatten()
+            .any(|path| {
+                paths
+                    .iter()
+                    .any(|prefix| path.starts_with(prefix))
+            })
+    }))
 }
 
 /// Prompt user which part of the version to increment
-fn prompt_increment(version: Option<Version>) -> anyhow::Result<Version> {
+fn prompt_increment(version: Option<Version>, config: &Config) -> anyhow::Result<Version> {
     let items = SubVersion::VARIANTS;
+    let default = config
+        .default_subversion
+        .map(subversion_index)
+        .unwrap_or(2);
     let selection = Select::with_theme(&ColorfulTheme::default())
         .items(items)
         .with_prompt("Subversion to increment")
-        .default(2)
+        .default(default)
         .interact()?;
     Ok(version
-        .unwrap_or_default()
+        .unwrap_or_else(|| Version::default_initial(config.tag_prefix(), config.prerelease_identifier()))
         .increment_version(SubVersion::from_str(
             items.get(selection).context("Invalid selection")?,
         )?))
 }
+
+fn subversion_index(part: SubVersion) -> usize {
+    match part {
+        SubVersion::Major => 0,
+        SubVersion::Minor => 1,
+        SubVersion::Patch => 2,
+    }
+}
@@ -1,7 +1,7 @@
 use anyhow::Context;
 use clap::Parser;
 use git2::Repository;
-use tagger::{tagger, version::Version};
+use tagger::{config::Config, tagger, version::Version, TaggerOptions};
 
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None)]
@@ -20,18 +20,69 @@ struct Args {
     /// the user.
     #[clap(short)]
     yes: bool,
+
+    /// Determine the version bump automatically from the Conventional
+    /// Commits since the last release, instead of prompting interactively.
+    /// Falls back to the interactive prompt if no commit matches.
+    #[clap(long)]
+    conventional: bool,
+
+    /// Also prepend the rendered changelog section to CHANGELOG.md at the
+    /// repo root.
+    #[clap(long)]
+    changelog: bool,
+
+    /// Scope version and changelog computation to commits touching this
+    /// path prefix. Repeatable; overrides the config file's `paths` when
+    /// given.
+    #[clap(long = "path")]
+    paths: Vec<String>,
+
+    /// Derive a MinVer-style prerelease version from the commit height to
+    /// the nearest tag, without prompting. Useful for CI builds.
+    #[clap(long)]
+    height: bool,
+
+    /// Resolve the next version without creating or pushing a tag, and
+    /// print it to stdout instead.
+    #[clap(long)]
+    print: bool,
+
+    /// With `--print`, print the HEAD commit sha associated with the
+    /// resolved version instead of the version string.
+    #[clap(long)]
+    commit_sha: bool,
 }
 
 fn main() -> anyhow::Result<()> {
     let args = Args::parse();
 
     let path = std::env::current_dir()?;
-    let repo = Repository::open(path)?;
+    let repo = Repository::open(&path)?;
+    let config = Config::load(repo.workdir().unwrap_or(&path))?;
 
     let next_version = match args.name {
-        Some(name) => Some(Version::parse(&name).context("Not a valid version string")?),
+        Some(name) => Some(
+            Version::parse(&name, config.tag_prefix(), config.prerelease_identifier())
+                .context("Not a valid version string")?,
+        ),
         None => None,
     };
 
-    tagger(&repo, next_version, args.interactive_editor, !args.yes)
+    let options = TaggerOptions {
+        next_version,
+        interactive_editor: args.interactive_editor || config.interactive_editor.unwrap_or(false),
+        prompt_push: !(args.yes || config.auto_push.unwrap_or(false)),
+        conventional: args.conventional,
+        append_changelog: args.changelog,
+        paths: match args.paths.is_empty() {
+            true => config.paths.clone(),
+            false => args.paths,
+        },
+        height: args.height,
+        print: args.print,
+        commit_sha: args.commit_sha,
+    };
+
+    tagger(&repo, options, &config)
 }
@@ -1,14 +1,23 @@
+use std::cmp::Ordering;
 use std::fmt::Display;
 
 use anyhow::{Context, Result};
 use semver::{Prerelease, Version as Semver};
+use serde::Deserialize;
 use strum_macros::{EnumString, EnumVariantNames};
-use substring::Substring;
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, Clone)]
-pub struct Version(pub Semver);
+/// A tag version, carrying the [`Config`](crate::config::Config)-derived
+/// prefix and prerelease identifier it was created with so it can format and
+/// increment itself without those being threaded through every call site.
+#[derive(Clone)]
+pub struct Version {
+    pub semver: Semver,
+    prefix: String,
+    prerelease_identifier: String,
+}
 
-#[derive(EnumString, EnumVariantNames)]
+#[derive(EnumString, EnumVariantNames, Clone, Copy, PartialEq, Eq, Debug, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum SubVersion {
     Major,
     Minor,
@@ -16,29 +25,63 @@ pub enum SubVersion {
 }
 
 impl Version {
+    pub fn new(semver: Semver, prefix: &str, prerelease_identifier: &str) -> Self {
+        Self {
+            semver,
+            prefix: prefix.to_string(),
+            prerelease_identifier: prerelease_identifier.to_string(),
+        }
+    }
+
+    /// The default starting version (`0.1.0`) for a repo with no prior tags.
+    pub fn default_initial(prefix: &str, prerelease_identifier: &str) -> Self {
+        Self::new(Semver::new(0, 1, 0), prefix, prerelease_identifier)
+    }
+
+    /// Increments the trailing numeric segment of the prerelease by `i`. A
+    /// height-encoded prerelease (e.g. `pre.0.3`, dotted) has only its
+    /// trailing `.<height>` segment bumped, preserving the rest of the
+    /// string; a plain prerelease (e.g. `pre3`) has its trailing digits
+    /// bumped as before.
     pub fn increment_prerelease(self, i: i32) -> Result<Self> {
-        let re = lazy_regex::regex!(r"pre(\d+)");
-        let version_str = self.0.pre.as_str();
+        let version_str = self.semver.pre.as_str();
+        if version_str.is_empty() {
+            return Ok(self.set_prerelease(0));
+        }
 
-        let new_pre_version = match version_str {
-            "" => 0,
-            _ => {
+        match version_str.rsplit_once('.') {
+            Some((base, height)) => {
+                let height: i32 = height
+                    .parse()
+                    .context("Failed to parse height segment of pre-version string")?;
+                let value = format!("{}.{}", base, height + i);
+                Ok(self.set_prerelease_str(value))
+            }
+            None => {
+                let re = lazy_regex::regex!(r"(\d+)$");
                 let cap = re
                     .captures(version_str)
                     .context("Failed to parse pre-version string")?;
                 let pre_tag_version: i32 = cap[1].parse()?;
-                pre_tag_version + i
+                Ok(self.set_prerelease(pre_tag_version + i))
             }
-        };
-        Ok(self.set_prerelease(new_pre_version))
+        }
     }
 
     pub fn set_prerelease(mut self, i: i32) -> Self {
-        self.0.pre = Prerelease::new(format!("pre{}", i).as_str())
+        self.semver.pre = Prerelease::new(format!("{}{}", self.prerelease_identifier, i).as_str())
             .expect("Could not set pre-version string");
         self
     }
 
+    /// Sets the prerelease to the literal `value`, unlike [`Self::set_prerelease`]
+    /// which always rebuilds it from the identifier and a plain number. Used
+    /// to bump a dotted, height-encoded prerelease in place.
+    fn set_prerelease_str(mut self, value: String) -> Self {
+        self.semver.pre = Prerelease::new(&value).expect("Could not set pre-version string");
+        self
+    }
+
     pub fn resolve_collision(self, pre_tags: &[Self]) -> Result<Self> {
         match pre_tags.contains(&self) {
             true => self.increment_prerelease(100)?.resolve_collision(pre_tags),
@@ -49,25 +92,51 @@ impl Version {
     pub fn increment_version(mut self, part: SubVersion) -> Self {
         match part {
             SubVersion::Major => {
-                self.0.major += 1;
-                self.0.minor = 0;
-                self.0.patch = 0;
+                self.semver.major += 1;
+                self.semver.minor = 0;
+                self.semver.patch = 0;
             }
             SubVersion::Minor => {
-                self.0.minor += 1;
-                self.0.patch = 0;
+                self.semver.minor += 1;
+                self.semver.patch = 0;
             }
             SubVersion::Patch => {
-                self.0.patch += 1;
+                self.semver.patch += 1;
             }
         };
         self
     }
 
-    pub fn parse(name: &str) -> Result<Self> {
-        let semver_str = name.substring(1, name.len());
-        let version = Version(Semver::parse(semver_str)?);
-        Ok(version)
+    pub fn is_prerelease(&self) -> bool {
+        !self.semver.pre.is_empty()
+    }
+
+    /// Sets the prerelease to `<identifier>.0.<height>`, as used by the
+    /// MinVer-style height mode when bumping past the nearest release tag.
+    pub fn with_height(mut self, height: u32, identifier: &str) -> Self {
+        self.semver.pre = Prerelease::new(format!("{}.0.{}", identifier, height).as_str())
+            .expect("Could not set height pre-release string");
+        self
+    }
+
+    /// Appends `height` to the existing prerelease string, as used by the
+    /// MinVer-style height mode when the nearest tag is already a
+    /// prerelease.
+    pub fn append_height(mut self, height: u32) -> Self {
+        let appended = match self.semver.pre.is_empty() {
+            true => height.to_string(),
+            false => format!("{}.{}", self.semver.pre.as_str(), height),
+        };
+        self.semver.pre =
+            Prerelease::new(&appended).expect("Could not set height pre-release string");
+        self
+    }
+
+    pub fn parse(name: &str, prefix: &str, prerelease_identifier: &str) -> Result<Self> {
+        let semver_str = name
+            .strip_prefix(prefix)
+            .with_context(|| format!("Version `{}` does not start with prefix `{}`", name, prefix))?;
+        Ok(Self::new(Semver::parse(semver_str)?, prefix, prerelease_identifier))
     }
 
     pub fn git_ref(&self) -> String {
@@ -75,14 +144,28 @@ impl Version {
     }
 }
 
-impl Default for Version {
-    fn default() -> Self {
-        Self(Semver::new(0, 1, 0))
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.semver == other.semver
+    }
+}
+
+impl Eq for Version {}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.semver.cmp(&other.semver)
     }
 }
 
 impl Display for Version {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_fmt(format_args!("v{}", self.0))
+        f.write_fmt(format_args!("{}{}", self.prefix, self.semver))
     }
 }